@@ -1,21 +1,127 @@
 #![allow(dead_code)]
 use parking_lot::RwLock;
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::cell::{Cell, RefCell};
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Weak};
 
 type FateFn<T> = dyn Fn() -> T + Send + Sync + 'static;
+type ObserverList<T> = Vec<(u64, Box<dyn FnMut(&T) + Send + Sync>)>;
 
 pub trait FateTrait: Send + Sync {
     fn is_dirty(&self) -> bool;
     fn set_dirty(&self);
-    fn add_dependent(&self, dependent: Box<dyn FateTrait>);
-    fn remove_dependent(&self, dependent: Box<dyn FateTrait>);
+    fn add_dependent(&self, dependent: Arc<dyn FateTrait>);
+    fn remove_dependent(&self, dependent: Arc<dyn FateTrait>);
     fn get_id(&self) -> usize;
+    fn strong_handle(&self) -> Arc<dyn FateTrait>;
+    fn has_observers(&self) -> bool;
+    fn flush_if_pending(&self);
+    fn remove_observer(&self, id: u64);
+    // This node's own dependencies, so a candidate dependency can be walked
+    // transitively to check whether accepting it would close a cycle back
+    // to some other node (see `creates_cycle`).
+    fn dependency_handles(&self) -> Vec<Arc<dyn FateTrait>>;
+}
+
+// Tracks the node currently being (re)computed so that `Fate::get` can
+// discover its dependencies automatically instead of requiring a
+// hand-written dependency list. One frame is pushed per nested
+// `from_tracked_expression` evaluation, so reads performed by a tracked
+// expression that itself reads another tracked expression are attributed
+// to the innermost (topmost) frame.
+struct TrackingFrame {
+    id: usize,
+    owner: Arc<dyn FateTrait>,
+    old_ids: HashSet<usize>,
+    collected_ids: HashSet<usize>,
+    collected: Vec<Arc<dyn FateTrait>>,
+}
+
+thread_local! {
+    static TRACKING_STACK: RefCell<Vec<TrackingFrame>> = const { RefCell::new(Vec::new()) };
+    static IN_PROGRESS: RefCell<HashSet<usize>> = RefCell::new(HashSet::new());
+    // How many nested `set_dirty` calls are on this thread's stack right
+    // now, and which observed nodes went dirty somewhere in that stack.
+    // Flushing is deferred until the outermost call unwinds, so an observer
+    // always sees a value computed after the whole propagation settled
+    // rather than a half-updated graph.
+    static PROPAGATION_DEPTH: Cell<u32> = const { Cell::new(0) };
+    static PENDING_FLUSH: RefCell<Vec<Arc<dyn FateTrait>>> = RefCell::new(Vec::new());
+}
+
+/// Returned by [`Fate::try_get`] when the node it is called on is already
+/// being computed further up the same thread's call stack (a dependency
+/// cycle back to that node).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CycleError;
+
+impl std::fmt::Display for CycleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Fate dependency cycle detected")
+    }
+}
+
+impl std::error::Error for CycleError {}
+
+// Whether making `dependencies` the dependency set of `root_id` would close
+// a cycle, i.e. whether `root_id` is transitively reachable from any of
+// them. A rebind that would create such a cycle must be rejected rather
+// than accepted, since the new expression's closure captures a strong
+// handle to each dependency, and a strong cycle through those closures is
+// never freed.
+fn creates_cycle(root_id: usize, dependencies: &[Arc<dyn FateTrait>]) -> bool {
+    let mut visited: HashSet<usize> = HashSet::new();
+    let mut stack: Vec<Arc<dyn FateTrait>> = dependencies.to_vec();
+    while let Some(node) = stack.pop() {
+        let id = node.get_id();
+        if id == root_id {
+            return true;
+        }
+        if visited.insert(id) {
+            stack.extend(node.dependency_handles());
+        }
+    }
+    false
+}
+
+// RAII guard that removes `id` from the in-progress set on the way out,
+// including via unwinding, so a panicking expression can't leave the id
+// permanently marked as in-progress on this thread.
+struct InProgressGuard(usize);
+
+impl Drop for InProgressGuard {
+    fn drop(&mut self) {
+        IN_PROGRESS.with(|in_progress| {
+            in_progress.borrow_mut().remove(&self.0);
+        });
+    }
+}
+
+// Called at the top of every `Fate::get`. If a tracked expression is
+// currently recomputing (the stack is non-empty), registers `node` as one
+// of its dependencies, skipping re-entrant self-reads and de-duplicating
+// repeated reads within the same evaluation pass.
+fn track_read(node: &dyn FateTrait) {
+    TRACKING_STACK.with(|stack| {
+        let mut stack = stack.borrow_mut();
+        if let Some(frame) = stack.last_mut() {
+            let id = node.get_id();
+            if id == frame.id || !frame.collected_ids.insert(id) {
+                return;
+            }
+            if !frame.old_ids.contains(&id) {
+                node.add_dependent(frame.owner.clone());
+            }
+            frame.collected.push(node.strong_handle());
+        }
+    });
 }
 
 enum Binding<T> {
     Value(T),
     Expression(Box<FateFn<T>>),
+    TrackedExpression(Box<FateFn<T>>),
 }
 
 impl<T: Default> Default for Binding<T> {
@@ -26,70 +132,258 @@ impl<T: Default> Default for Binding<T> {
 
 #[derive(Default)]
 struct FateDependencies {
-    dependencies: Vec<Box<dyn FateTrait>>,
-    dependents: Vec<Box<dyn FateTrait>>,
+    dependencies: Vec<Arc<dyn FateTrait>>,
+    dependents: Vec<Weak<dyn FateTrait>>,
 }
 
-#[derive(Default, Clone)]
-pub struct Fate<T: Clone> {
-    cached_value: Arc<RwLock<T>>,
-    dirty: Arc<AtomicBool>,
-    dependencies: Arc<RwLock<FateDependencies>>,
-    data: Arc<RwLock<Binding<T>>>,
+// The shared state behind every `Fate<T>` handle. It lives behind a single
+// `Arc`, and keeps a `Weak` back-reference to itself so that a `&FateInner<T>`
+// (all the trait methods get) can still hand out a fresh strong or weak
+// handle to the same allocation on demand.
+struct FateInner<T> {
+    self_weak: Weak<FateInner<T>>,
+    cached_value: RwLock<T>,
+    dirty: AtomicBool,
+    dependencies: RwLock<FateDependencies>,
+    data: RwLock<Binding<T>>,
+    next_observer_id: AtomicU64,
+    observers: RwLock<ObserverList<T>>,
+}
+
+impl<T: 'static + Clone + Send + Sync> FateInner<T> {
+    fn get(&self) -> T {
+        self.try_get()
+            .unwrap_or_else(|_| self.cached_value.read().clone())
+    }
+
+    fn try_get(&self) -> Result<T, CycleError> {
+        track_read(self);
+        let id = self.get_id();
+        let newly_entered = IN_PROGRESS.with(|in_progress| in_progress.borrow_mut().insert(id));
+        if !newly_entered {
+            return Err(CycleError);
+        }
+        let _guard = InProgressGuard(id);
+
+        let result = if self.is_dirty() {
+            let data = self.data.write();
+            let result = match &*data {
+                Binding::Value(value) => value.clone(),
+                Binding::Expression(expression) => expression(),
+                Binding::TrackedExpression(expression) => self.evaluate_tracked(expression),
+            };
+            let mut cached_value = self.cached_value.write();
+            *cached_value = result.clone();
+            // The value is now up to date with `data`; the next write is what
+            // should make us dirty again, not a write that already happened.
+            self.dirty.store(false, Ordering::Release);
+            result
+        } else {
+            self.cached_value.read().clone()
+        };
+        Ok(result)
+    }
+
+    // Runs `expression` with a tracking frame pushed for `self`, then diffs
+    // the dependencies it actually read (`track_read` calls made during the
+    // call) against the previously stored ones: dropped dependencies have
+    // `self` removed as their dependent, and the fresh set becomes the new
+    // `dependencies` list. New edges are registered live, as they're read,
+    // by `track_read` itself.
+    fn evaluate_tracked(&self, expression: &FateFn<T>) -> T {
+        let old_ids: HashSet<usize> = {
+            let deps = self.dependencies.read();
+            deps.dependencies.iter().map(|dep| dep.get_id()).collect()
+        };
+        let frame = TrackingFrame {
+            id: self.get_id(),
+            owner: self.strong_handle(),
+            old_ids,
+            collected_ids: HashSet::new(),
+            collected: Vec::new(),
+        };
+        TRACKING_STACK.with(|stack| stack.borrow_mut().push(frame));
+        let result = expression();
+        let frame = TRACKING_STACK
+            .with(|stack| stack.borrow_mut().pop())
+            .expect("tracking frame pushed above was not popped");
+
+        let mut deps = self.dependencies.write();
+        for old_dep in deps.dependencies.drain(..) {
+            if !frame.collected_ids.contains(&old_dep.get_id()) {
+                old_dep.remove_dependent(self.strong_handle());
+            }
+        }
+        deps.dependencies = frame.collected;
+
+        result
+    }
 }
 
-impl<T: 'static + Clone + Send + Sync> FateTrait for Fate<T> {
+impl<T: 'static + Clone + Send + Sync + PartialEq> FateInner<T> {
+    fn subscribe(&self, callback: impl Fn(&T) + Send + Sync + 'static) -> SubscriptionHandle {
+        let id = self.next_observer_id.fetch_add(1, Ordering::Relaxed);
+        let mut last_delivered: Option<T> = Some(self.get());
+        let notify: Box<dyn FnMut(&T) + Send + Sync> = Box::new(move |value: &T| {
+            if last_delivered.as_ref() != Some(value) {
+                callback(value);
+                last_delivered = Some(value.clone());
+            }
+        });
+        self.observers.write().push((id, notify));
+        SubscriptionHandle {
+            id,
+            node: self.self_weak.clone(),
+        }
+    }
+}
+
+impl<T: 'static + Clone + Send + Sync> FateTrait for FateInner<T> {
     fn is_dirty(&self) -> bool {
         self.dirty.load(Ordering::Acquire)
     }
 
     fn set_dirty(&self) {
+        if self.is_dirty() {
+            // Already dirty (and therefore already propagated to our
+            // dependents on a prior call). Returning here both breaks cycles
+            // in the dependency graph and avoids re-walking the same
+            // diamond-shaped subgraph once per incoming path.
+            return;
+        }
         self.dirty.store(true, Ordering::Release);
-        let data = self.dependencies.read();
-        for dependent in &data.dependents {
-            dependent.set_dirty();
+        PROPAGATION_DEPTH.with(|depth| depth.set(depth.get() + 1));
+        if self.has_observers() {
+            PENDING_FLUSH.with(|pending| pending.borrow_mut().push(self.strong_handle()));
+        }
+
+        let mut data = self.dependencies.write();
+        // Dependents are weak, since a node may be dropped while other nodes
+        // still list it as a dependent; prune any that no longer upgrade.
+        data.dependents.retain(|dependent| {
+            if let Some(dependent) = dependent.upgrade() {
+                dependent.set_dirty();
+                true
+            } else {
+                false
+            }
+        });
+        drop(data);
+
+        let still_propagating = PROPAGATION_DEPTH.with(|depth| {
+            let remaining = depth.get() - 1;
+            depth.set(remaining);
+            remaining > 0
+        });
+        if !still_propagating {
+            let settled: Vec<Arc<dyn FateTrait>> =
+                PENDING_FLUSH.with(|pending| pending.borrow_mut().drain(..).collect());
+            for node in settled {
+                node.flush_if_pending();
+            }
         }
     }
 
-    fn add_dependent(&self, dependent: Box<dyn FateTrait>) {
+    fn add_dependent(&self, dependent: Arc<dyn FateTrait>) {
         let mut data = self.dependencies.write();
-        data.dependents.push(dependent);
+        data.dependents.push(Arc::downgrade(&dependent));
     }
 
-    fn remove_dependent(&self, dependent: Box<dyn FateTrait>) {
+    fn remove_dependent(&self, dependent: Arc<dyn FateTrait>) {
         let mut data = self.dependencies.write();
-        let index = data
-            .dependents
-            .iter()
-            .position(|dep| dep.get_id() == dependent.get_id());
-        if let Some(index) = index {
-            data.dependents.remove(index);
-        }
+        let id = dependent.get_id();
+        data.dependents
+            .retain(|dep| dep.upgrade().map(|dep| dep.get_id() != id).unwrap_or(true));
     }
 
     fn get_id(&self) -> usize {
-        Arc::as_ptr(&self.dependencies) as usize
+        self as *const Self as usize
+    }
+
+    fn strong_handle(&self) -> Arc<dyn FateTrait> {
+        self.self_weak
+            .upgrade()
+            .expect("FateInner outlives any &self call into it")
+    }
+
+    fn has_observers(&self) -> bool {
+        !self.observers.read().is_empty()
+    }
+
+    fn flush_if_pending(&self) {
+        let value = self.get();
+        let mut observers = self.observers.write();
+        for (_, notify) in observers.iter_mut() {
+            notify(&value);
+        }
+    }
+
+    fn remove_observer(&self, id: u64) {
+        self.observers.write().retain(|(oid, _)| *oid != id);
+    }
+
+    fn dependency_handles(&self) -> Vec<Arc<dyn FateTrait>> {
+        self.dependencies.read().dependencies.clone()
+    }
+}
+
+/// A live registration made by [`Fate::subscribe`]. Dropping it unregisters
+/// the callback; there is no other way to cancel a subscription.
+pub struct SubscriptionHandle {
+    id: u64,
+    node: Weak<dyn FateTrait>,
+}
+
+impl Drop for SubscriptionHandle {
+    fn drop(&mut self) {
+        if let Some(node) = self.node.upgrade() {
+            node.remove_observer(self.id);
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Fate<T: Clone> {
+    inner: Arc<FateInner<T>>,
+}
+
+impl<T: 'static + Clone + Send + Sync + Default> Default for Fate<T> {
+    fn default() -> Self {
+        Fate::from_value(T::default())
     }
 }
 
 impl<T: 'static + Clone + Send + Sync> Fate<T> {
+    /// A strong, type-erased handle to this node, suitable for registering it
+    /// as someone else's dependency or dependent.
+    pub fn handle(&self) -> Arc<dyn FateTrait> {
+        self.inner.clone()
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.inner.is_dirty()
+    }
+
+    fn set_dirty(&self) {
+        self.inner.set_dirty();
+    }
+
     pub fn get(&self) -> T {
-        if self.is_dirty() {
-            let data = self.data.write();
-            let result = match &*data {
-                Binding::Value(value) => value.clone(),
-                Binding::Expression(expression) => expression(),
-            };
-            let mut cached_value = self.cached_value.write();
-            *cached_value = result.clone();
-            result
-        } else {
-            self.cached_value.read().clone()
-        }
+        self.inner.get()
+    }
+
+    /// Like [`Fate::get`], but if this node is already being recomputed
+    /// further up the same thread's call stack (a dependency cycle back to
+    /// it), returns `Err(CycleError)` instead of its last cached value. A
+    /// cycle met deeper inside this node's own recomputation, through plain
+    /// `get` calls on other nodes, is not reported: those reads return the
+    /// other nodes' last cached values.
+    pub fn try_get(&self) -> Result<T, CycleError> {
+        self.inner.try_get()
     }
 
     pub fn by_ref(&self, ref_fn: impl FnOnce(&T)) {
-        let data = self.data.read();
+        let data = self.inner.data.read();
         if let Binding::Value(value) = &*data {
             ref_fn(value);
         }
@@ -98,7 +392,7 @@ impl<T: 'static + Clone + Send + Sync> Fate<T> {
     pub fn by_ref_mut(&self, mut_ref_fn: impl FnOnce(&mut T)) {
         let mut dirtied = false;
         {
-            let mut data = self.data.write();
+            let mut data = self.inner.data.write();
             if let Binding::Value(value) = &mut *data {
                 mut_ref_fn(value);
 
@@ -112,81 +406,189 @@ impl<T: 'static + Clone + Send + Sync> Fate<T> {
 
     pub fn bind_value(&self, value: T) {
         {
-            let mut data = self.data.write();
+            let mut data = self.inner.data.write();
             *data = Binding::Value(value);
         }
         self.set_dirty();
     }
 
+    /// Rebinds this node to a new expression and dependency set. Returns
+    /// `Err(CycleError)` and leaves this node untouched if `dependencies`
+    /// would create a cycle back to this node, since storing such an
+    /// expression would capture a strong reference cycle that is never
+    /// freed.
     pub fn bind_expression(
         &self,
         expression: Box<FateFn<T>>,
-        dependencies: Vec<Box<dyn FateTrait>>,
-    ) {
-        self.set_dependencies(dependencies);
+        dependencies: Vec<Arc<dyn FateTrait>>,
+    ) -> Result<(), CycleError> {
+        self.set_dependencies(dependencies)?;
         {
-            let mut data = self.data.write();
+            let mut data = self.inner.data.write();
             *data = Binding::Expression(expression);
         }
         self.set_dirty();
+        Ok(())
     }
 
     pub fn from_value(value: T) -> Fate<T> {
-        Fate {
-            cached_value: Arc::new(RwLock::new(value.clone())),
-            dirty: Arc::new(AtomicBool::new(false)),
-            data: Arc::new(RwLock::new(Binding::Value(value))),
-            dependencies: Arc::new(RwLock::new(FateDependencies {
-                dependencies: vec![],
-                dependents: vec![],
-            })),
-        }
+        let inner = Arc::new_cyclic(|self_weak| FateInner {
+            self_weak: self_weak.clone(),
+            cached_value: RwLock::new(value.clone()),
+            dirty: AtomicBool::new(false),
+            data: RwLock::new(Binding::Value(value)),
+            dependencies: RwLock::new(FateDependencies::default()),
+            next_observer_id: AtomicU64::new(0),
+            observers: RwLock::new(Vec::new()),
+        });
+        Fate { inner }
     }
 
     pub fn from_expression(
         expression: Box<FateFn<T>>,
-        dependencies: Vec<Box<dyn FateTrait>>,
+        dependencies: Vec<Arc<dyn FateTrait>>,
     ) -> Fate<T> {
-        let result = Fate {
-            cached_value: Arc::new(RwLock::new(expression())),
-            dirty: Arc::new(AtomicBool::new(false)),
-            data: Arc::new(RwLock::new(Binding::Expression(expression))),
-            dependencies: Arc::new(RwLock::new(FateDependencies {
-                dependencies: vec![],
-                dependents: vec![],
-            })),
-        };
-        result.set_dependencies(dependencies);
+        let inner = Arc::new_cyclic(|self_weak| FateInner {
+            self_weak: self_weak.clone(),
+            cached_value: RwLock::new(expression()),
+            dirty: AtomicBool::new(false),
+            data: RwLock::new(Binding::Expression(expression)),
+            dependencies: RwLock::new(FateDependencies::default()),
+            next_observer_id: AtomicU64::new(0),
+            observers: RwLock::new(Vec::new()),
+        });
+        let result = Fate { inner };
+        result
+            .set_dependencies(dependencies)
+            .expect("a freshly constructed Fate cannot already be part of a dependency cycle");
+        result
+    }
+
+    /// Like [`Fate::from_expression`], but the dependency list is discovered
+    /// automatically from whichever `Fate`s the expression calls `.get()` on,
+    /// instead of being hand-written. Dependencies are re-derived on every
+    /// recomputation, so they can change across conditional branches.
+    ///
+    /// Unlike [`Fate::bind_expression`], a dependency cycle formed this way
+    /// is not detected or rejected: because the dependency set is only
+    /// discovered by actually running `expression`, any cycle it closes has
+    /// already been captured by a closure (here or on the other end) before
+    /// there is anything to check. Such a cycle still can't hang or recurse
+    /// forever (`try_get`'s in-progress guard still applies), but the nodes
+    /// involved keep each other alive forever via real strong references.
+    /// Avoiding that shape is the caller's responsibility.
+    pub fn from_tracked_expression(expression: Box<FateFn<T>>) -> Fate<T>
+    where
+        T: Default,
+    {
+        let inner = Arc::new_cyclic(|self_weak| FateInner {
+            self_weak: self_weak.clone(),
+            cached_value: RwLock::new(T::default()),
+            dirty: AtomicBool::new(true),
+            data: RwLock::new(Binding::TrackedExpression(expression)),
+            dependencies: RwLock::new(FateDependencies::default()),
+            next_observer_id: AtomicU64::new(0),
+            observers: RwLock::new(Vec::new()),
+        });
+        let result = Fate { inner };
+        result.get();
         result
     }
 
+    /// Derives a new `Fate` that tracks `f` applied to this node's value,
+    /// recomputing whenever this node changes. Equivalent to writing a
+    /// `from_expression` by hand with a single dependency on `self`.
+    pub fn map<U: 'static + Clone + Send + Sync>(
+        &self,
+        f: impl Fn(&T) -> U + Send + Sync + 'static,
+    ) -> Fate<U> {
+        let source = self.clone();
+        Fate::from_expression(Box::new(move || f(&source.get())), vec![self.handle()])
+    }
+
+    /// Derives a new `Fate` that combines this node's value with `other`'s
+    /// via `f`, recomputing whenever either changes.
+    pub fn zip2<U: 'static + Clone + Send + Sync, V: 'static + Clone + Send + Sync>(
+        &self,
+        other: &Fate<U>,
+        f: impl Fn(&T, &U) -> V + Send + Sync + 'static,
+    ) -> Fate<V> {
+        let source = self.clone();
+        let other_clone = other.clone();
+        Fate::from_expression(
+            Box::new(move || f(&source.get(), &other_clone.get())),
+            vec![self.handle(), other.handle()],
+        )
+    }
+
+    /// Derives a new `Fate` that mirrors this node's value whenever `pred`
+    /// accepts it, and otherwise keeps holding the last accepted value (or
+    /// `default` if `pred` has never accepted one yet).
+    pub fn filter(&self, pred: impl Fn(&T) -> bool + Send + Sync + 'static, default: T) -> Fate<T> {
+        let source = self.clone();
+        let last = RwLock::new(default);
+        Fate::from_expression(
+            Box::new(move || {
+                let value = source.get();
+                if pred(&value) {
+                    *last.write() = value.clone();
+                    value
+                } else {
+                    last.read().clone()
+                }
+            }),
+            vec![self.handle()],
+        )
+    }
+
     fn clear_dependencies(&self) {
         self.remove_all_dependencies();
-        let mut data = self.dependencies.write();
+        let mut data = self.inner.dependencies.write();
         data.dependencies.clear();
     }
 
     fn remove_all_dependencies(&self) {
-        let data = self.dependencies.read();
+        let data = self.inner.dependencies.read();
         for dependency in &data.dependencies {
-            dependency.remove_dependent(Box::new(self.clone()));
+            dependency.remove_dependent(self.handle());
         }
     }
 
-    fn set_dependencies(&self, dependencies: Vec<Box<dyn FateTrait>>) {
+    // Rejects `dependencies` up front if accepting them would close a cycle
+    // back to this node, before anything is mutated: the new expression
+    // (not stored yet by the caller at this point) would otherwise capture
+    // a strong handle to each dependency, and a cycle through those strong
+    // handles is never freed.
+    fn set_dependencies(&self, dependencies: Vec<Arc<dyn FateTrait>>) -> Result<(), CycleError> {
+        if creates_cycle(self.inner.get_id(), &dependencies) {
+            return Err(CycleError);
+        }
         self.remove_all_dependencies();
-        let mut data = self.dependencies.write();
+        let mut data = self.inner.dependencies.write();
         data.dependencies = dependencies;
         for dependency in &data.dependencies {
-            dependency.add_dependent(Box::new(self.clone()));
+            dependency.add_dependent(self.handle());
         }
+        Ok(())
+    }
+}
+
+impl<T: 'static + Clone + Send + Sync + PartialEq> Fate<T> {
+    /// Registers `callback` to fire whenever this node's computed value
+    /// changes. The callback runs once the dependency graph has finished
+    /// settling after a change, not eagerly on every `set_dirty`, so it
+    /// never sees a partially-updated graph. Dropping the returned
+    /// [`SubscriptionHandle`] unregisters the callback.
+    pub fn subscribe(&self, callback: impl Fn(&T) + Send + Sync + 'static) -> SubscriptionHandle {
+        self.inner.subscribe(callback)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Fate;
+    use super::{CycleError, Fate};
     use fates_macro::fate;
+    use std::sync::{Arc, Mutex};
     use std::thread;
 
     #[test]
@@ -197,7 +599,7 @@ mod tests {
         let b_clone = b.clone();
         let c = Fate::from_expression(
             Box::new(move || a_clone.get() + b_clone.get()),
-            vec![Box::new(a.clone()), Box::new(b.clone())],
+            vec![a.handle(), b.handle()],
         );
         assert_eq!(c.get(), 8);
         b.bind_value(100);
@@ -212,7 +614,7 @@ mod tests {
         let b_clone = b.clone();
         let c = Fate::from_expression(
             Box::new(move || a_clone.get() + b_clone.get() * b_clone.get()),
-            vec![Box::new(a.clone()), Box::new(b.clone())],
+            vec![a.handle(), b.handle()],
         );
         assert_eq!(c.get(), 10 + 23 * 23);
         b.bind_value(113);
@@ -222,7 +624,7 @@ mod tests {
         let a_clone = a.clone();
         let d = Fate::from_expression(
             Box::new(move || c_clone.get() * a_clone.get()),
-            vec![Box::new(c.clone()), Box::new(a.clone())],
+            vec![c.handle(), a.handle()],
         );
 
         assert_eq!(d.get(), (10 + 113 * 113) * 10);
@@ -233,12 +635,9 @@ mod tests {
         let e_clone = e.clone();
         c.bind_expression(
             Box::new(move || a_clone.get() * b_clone.get() / e_clone.get()),
-            vec![
-                Box::new(a.clone()),
-                Box::new(b.clone()),
-                Box::new(e.clone()),
-            ],
-        );
+            vec![a.handle(), b.handle(), e.handle()],
+        )
+        .unwrap();
         assert_eq!(c.get(), 10 * 113 / 2);
     }
 
@@ -256,21 +655,193 @@ mod tests {
         assert_eq!(c.get(), "cbc");
     }
 
-    fn circular_reference() {
+    #[test]
+    fn circular_reference_test() {
+        // Binding b back onto c, which already depends on b, would close a
+        // strong reference cycle through the two closures that is never
+        // freed. `bind_expression` now rejects it instead of completing the
+        // rebind, so neither the rebind nor the leak happens, and dropping
+        // every handle actually reclaims the whole graph.
+        let a_weak;
+        let b_weak;
+        let c_weak;
+        {
+            let (a, b, c) = circular_reference();
+            assert_eq!(c.get(), 8);
+            assert_eq!(b.get(), 5);
+            a_weak = Arc::downgrade(&a.handle());
+            b_weak = Arc::downgrade(&b.handle());
+            c_weak = Arc::downgrade(&c.handle());
+        }
+        assert!(a_weak.upgrade().is_none());
+        assert!(b_weak.upgrade().is_none());
+        assert!(c_weak.upgrade().is_none());
+    }
+
+    #[test]
+    fn try_get_detects_cycle_test() {
+        let a = Fate::from_value(1);
+        let a_for_expr = a.clone();
+        a.bind_expression(
+            Box::new(move || match a_for_expr.try_get() {
+                Ok(value) => value + 1,
+                Err(CycleError) => 99,
+            }),
+            vec![],
+        )
+        .unwrap();
+        assert_eq!(a.get(), 99);
+    }
+
+    fn circular_reference() -> (Fate<i32>, Fate<i32>, Fate<i32>) {
         let a = Fate::from_value(3);
         let b = Fate::from_value(5);
         let a_clone = a.clone();
         let b_clone = b.clone();
         let c = Fate::from_expression(
             Box::new(move || a_clone.get() + b_clone.get()),
-            vec![Box::new(a.clone()), Box::new(b.clone())],
+            vec![a.handle(), b.handle()],
         );
         let a_clone = a.clone();
         let c_clone = c.clone();
-        b.bind_expression(
+        let result = b.bind_expression(
             Box::new(move || a_clone.get() + c_clone.get()),
-            vec![Box::new(a.clone()), Box::new(c.clone())],
+            vec![a.handle(), c.handle()],
         );
+        assert_eq!(result, Err(CycleError));
+        (a, b, c)
+    }
+
+    #[test]
+    fn tracked_expression_discovers_dependencies_test() {
+        let a = Fate::from_value(2);
+        let b = Fate::from_value(3);
+        let a_clone = a.clone();
+        let b_clone = b.clone();
+        let sum = Fate::from_tracked_expression(Box::new(move || a_clone.get() + b_clone.get()));
+        assert_eq!(sum.get(), 5);
+
+        a.bind_value(10);
+        assert_eq!(sum.get(), 13);
+
+        b.bind_value(100);
+        assert_eq!(sum.get(), 110);
+    }
+
+    #[test]
+    fn tracked_expression_branch_changes_dependencies_test() {
+        let use_x = Fate::from_value(true);
+        let x = Fate::from_value(1);
+        let y = Fate::from_value(2);
+        let use_x_clone = use_x.clone();
+        let x_clone = x.clone();
+        let y_clone = y.clone();
+        let picked = Fate::from_tracked_expression(Box::new(move || {
+            if use_x_clone.get() {
+                x_clone.get()
+            } else {
+                y_clone.get()
+            }
+        }));
+        assert_eq!(picked.get(), 1);
+
+        // `y` isn't a current dependency while `use_x` is true, so changing
+        // it doesn't dirty `picked` at all.
+        y.bind_value(200);
+        assert_eq!(picked.get(), 1);
+
+        // Flipping the branch re-derives dependencies from scratch: `picked`
+        // now depends on `y` instead of `x`.
+        use_x.bind_value(false);
+        assert_eq!(picked.get(), 200);
+
+        x.bind_value(999);
+        assert_eq!(picked.get(), 200);
+    }
+
+    #[test]
+    fn tracked_expression_self_read_test() {
+        // `node` ends up reading itself through `slot` once `slot` is bound
+        // to point back at it. `track_read`'s `id == frame.id` guard must
+        // ignore that read rather than registering `node` as its own
+        // dependent, and the existing in-progress guard falls back to the
+        // last cached value instead of recursing forever.
+        let slot: Fate<Option<Fate<i32>>> = Fate::from_value(None);
+        let slot_clone = slot.clone();
+        let node = Fate::from_tracked_expression(Box::new(move || {
+            slot_clone.get().map(|inner| inner.get()).unwrap_or(7)
+        }));
+        assert_eq!(node.get(), 7);
+
+        slot.bind_value(Some(node.clone()));
+        node.set_dirty();
+        assert_eq!(node.get(), 7);
+    }
+
+    #[test]
+    fn tracked_expression_mutual_cycle_leaks_test() {
+        // Two tracked expressions that read each other via a forward
+        // reference form a genuine `Arc` cycle: `a`'s closure strongly
+        // captures `slot` (which strongly holds `b`), and `b`'s closure
+        // strongly captures `a` directly. Nothing here goes through
+        // `set_dependencies`'s cycle check (dependencies are only
+        // discovered by running the expression, by which point the
+        // closures already exist), so this is never rejected the way a
+        // `bind_expression` cycle is -- it's documented on
+        // `Fate::from_tracked_expression` as the caller's responsibility to
+        // avoid, and this test records the leak rather than hiding it.
+        let slot: Fate<Option<Fate<i32>>> = Fate::from_value(None);
+        let slot_clone = slot.clone();
+        let a = Fate::from_tracked_expression(Box::new(move || {
+            slot_clone.get().map(|b| b.get()).unwrap_or(1)
+        }));
+        let a_clone = a.clone();
+        let b = Fate::from_tracked_expression(Box::new(move || a_clone.get() + 1));
+
+        slot.bind_value(Some(b.clone()));
+        a.set_dirty();
+        // Terminates (the in-progress guard still prevents infinite
+        // recursion): `b` reads `a`'s stale cached value of 1, so both
+        // settle on 2.
+        assert_eq!(a.get(), 2);
+        assert_eq!(b.get(), 2);
+
+        let a_weak = Arc::downgrade(&a.handle());
+        let b_weak = Arc::downgrade(&b.handle());
+        drop(slot);
+        drop(a);
+        drop(b);
+
+        assert!(a_weak.upgrade().is_some());
+        assert!(b_weak.upgrade().is_some());
+    }
+
+    #[test]
+    fn tracked_expression_cycle_propagation_test() {
+        // `bind_expression` rejects a two-node cycle (see
+        // `circular_reference_test`), but `from_tracked_expression` cycles
+        // (see `tracked_expression_mutual_cycle_leaks_test`) aren't
+        // rejected, so this builds a real multi-node cycle to exercise
+        // `set_dirty`'s "already dirty -> return" guard: repeatedly dirtying
+        // both ends must terminate instead of recursing forever around the
+        // loop.
+        let slot: Fate<Option<Fate<i32>>> = Fate::from_value(None);
+        let slot_clone = slot.clone();
+        let a = Fate::from_tracked_expression(Box::new(move || {
+            slot_clone.get().map(|b| b.get()).unwrap_or(0)
+        }));
+        let a_clone = a.clone();
+        let b = Fate::from_tracked_expression(Box::new(move || a_clone.get()));
+        slot.bind_value(Some(b.clone()));
+        a.set_dirty();
+        let _ = a.get();
+
+        for _ in 0..1000 {
+            a.set_dirty();
+            b.set_dirty();
+            assert_eq!(a.get(), 0);
+            assert_eq!(b.get(), 0);
+        }
     }
 
     #[test]
@@ -409,4 +980,90 @@ mod tests {
         assert_eq!(&hello.get(), "Hello, Sam");
         assert_eq!(&goodbye.get(), "Goodbye, Sam");
     }
+
+    #[test]
+    fn subscribe_test() {
+        let a = Fate::from_value(1);
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let seen_clone = seen.clone();
+        let subscription = a.subscribe(move |value| seen_clone.lock().unwrap().push(*value));
+
+        a.bind_value(1); // no change: observer should not fire
+        assert_eq!(*seen.lock().unwrap(), Vec::<i32>::new());
+
+        a.bind_value(2);
+        assert_eq!(*seen.lock().unwrap(), vec![2]);
+
+        a.bind_value(2); // unchanged again
+        assert_eq!(*seen.lock().unwrap(), vec![2]);
+
+        a.bind_value(3);
+        assert_eq!(*seen.lock().unwrap(), vec![2, 3]);
+
+        drop(subscription);
+        a.bind_value(4);
+        assert_eq!(*seen.lock().unwrap(), vec![2, 3]);
+    }
+
+    #[test]
+    fn subscribe_through_expression_test() {
+        let a = Fate::from_value(1);
+        let a_clone = a.clone();
+        let b = Fate::from_expression(Box::new(move || a_clone.get() * 10), vec![a.handle()]);
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let seen_clone = seen.clone();
+        let _subscription = b.subscribe(move |value| seen_clone.lock().unwrap().push(*value));
+
+        a.bind_value(2);
+        assert_eq!(*seen.lock().unwrap(), vec![20]);
+    }
+
+    #[test]
+    fn combinator_test() {
+        let first = Fate::from_value("Alex".to_string());
+        let last = Fate::from_value("Smith".to_string());
+        let full = first.zip2(&last, |f, l| format!("{f} {l}"));
+        assert_eq!(full.get(), "Alex Smith");
+
+        let shout = full.map(|name| name.to_uppercase());
+        assert_eq!(shout.get(), "ALEX SMITH");
+
+        last.bind_value("Jones".to_string());
+        assert_eq!(full.get(), "Alex Jones");
+        assert_eq!(shout.get(), "ALEX JONES");
+    }
+
+    #[test]
+    fn filter_test() {
+        let source = Fate::from_value(1);
+        let positive_even = source.filter(|value| *value > 0 && value % 2 == 0, 0);
+        assert_eq!(positive_even.get(), 0);
+
+        source.bind_value(4);
+        assert_eq!(positive_even.get(), 4);
+
+        source.bind_value(-2);
+        assert_eq!(positive_even.get(), 4);
+
+        source.bind_value(6);
+        assert_eq!(positive_even.get(), 6);
+    }
+
+    #[test]
+    fn destructure_test() {
+        fate! {
+            [a, b]
+            let a = 1;
+            let b = 2;
+            let (sum, product) = (a + b, a * b);
+        }
+
+        assert_eq!(sum.get(), 3);
+        assert_eq!(product.get(), 2);
+
+        a.bind_value(10);
+        assert_eq!(sum.get(), 12);
+        assert_eq!(product.get(), 20);
+    }
 }
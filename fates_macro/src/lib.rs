@@ -28,7 +28,7 @@ impl Fold for CloneFold<'_> {
             let value_ident = format_ident!("{}{}", i, VALUE_NAME);
 
             self.clones += &format!("let {} = {}.clone(); ", clone_ident, i);
-            self.dependencies += &format!("Box::new({}.clone()), ", i);
+            self.dependencies += &format!("{}.handle(), ", i);
             self.has_dependencies = true;
             let value_expr_str =
                 &format!("let {} = {}.get();", value_ident, clone_ident);
@@ -53,6 +53,58 @@ impl<'a> CloneFold<'a> {
     }
 }
 
+// A destructuring `let` binds several names at once to components of a
+// single right-hand-side expression (`let (a, b) = pair.get();`). We only
+// support binding each component directly to a plain name, not further
+// nested patterns, since each component becomes its own `Fate`.
+fn destructure_components(pat: &Pat) -> Result<Vec<(Ident, proc_macro2::TokenStream)>> {
+    fn component_ident(pat: &Pat) -> Result<Ident> {
+        match pat {
+            Pat::Ident(pat_ident) => Ok(pat_ident.ident.clone()),
+            _ => Err(syn::Error::new_spanned(
+                pat,
+                "fate! destructuring only supports binding a component directly to a name",
+            )),
+        }
+    }
+
+    match pat {
+        Pat::Tuple(tuple) => tuple
+            .elems
+            .iter()
+            .enumerate()
+            .map(|(index, elem)| {
+                let ident = component_ident(elem)?;
+                let index = syn::Index::from(index);
+                Ok((ident, quote! { .#index }))
+            })
+            .collect(),
+        Pat::Slice(slice) => slice
+            .elems
+            .iter()
+            .enumerate()
+            .map(|(index, elem)| {
+                let ident = component_ident(elem)?;
+                let index = syn::Index::from(index);
+                Ok((ident, quote! { [#index] }))
+            })
+            .collect(),
+        Pat::Struct(pat_struct) => pat_struct
+            .fields
+            .iter()
+            .map(|field_pat| {
+                let ident = component_ident(&field_pat.pat)?;
+                let member = &field_pat.member;
+                Ok((ident, quote! { .#member }))
+            })
+            .collect(),
+        _ => Err(syn::Error::new_spanned(
+            pat,
+            "fate! destructuring supports tuple, slice, and struct patterns",
+        )),
+    }
+}
+
 struct Fate {
     quotes: Vec<proc_macro2::TokenStream>,
 }
@@ -71,6 +123,7 @@ impl Parse for Fate {
                 }
             }
         }
+        let mut destructure_count: u32 = 0;
         while !input.is_empty() {
             let is_new = if input.peek(Token![let]) {
                 input.parse::<Token![let]>()?;
@@ -78,7 +131,17 @@ impl Parse for Fate {
             } else {
                 false
             };
-            let fate_ident: Ident = input.parse()?;
+            let pat: Pat = if is_new {
+                Pat::parse_single(input)?
+            } else {
+                Pat::Ident(syn::PatIdent {
+                    attrs: Vec::new(),
+                    by_ref: None,
+                    mutability: None,
+                    ident: input.parse()?,
+                    subpat: None,
+                })
+            };
             input.parse::<Token![=]>()?;
             let expr = input.parse::<Expr>()?;
             input.parse::<Token![;]>()?;
@@ -92,20 +155,54 @@ impl Parse for Fate {
             let value_expr: proc_macro2::TokenStream =
                 clone_fold.values.parse().unwrap();
 
+            let Pat::Ident(pat_ident) = &pat else {
+                // A destructuring `let`: evaluate the shared expression once
+                // into a hidden aggregate `Fate`, then fan it out into one
+                // `Fate` per bound name, each projecting out its component.
+                let components = destructure_components(&pat)?;
+                let aggregate_ident =
+                    format_ident!("_fate_aggregate_{}__fate__", destructure_count);
+                destructure_count += 1;
+
+                quotes.push(quote! {
+                    #clones;
+                    let #aggregate_ident = Fate::from_expression(
+                        Box::new(move || {#value_expr #fixed_expr}), vec![#dependencies]);
+                });
+                for (name, projection) in components {
+                    quotes.push(quote! {
+                        let #name = {
+                            let aggregate = #aggregate_ident.clone();
+                            Fate::from_expression(
+                                Box::new(move || aggregate.get() #projection),
+                                vec![#aggregate_ident.handle()])
+                        };
+                    });
+                }
+                continue;
+            };
+            let fate_ident = &pat_ident.ident;
+
             let quote = if clone_fold.has_dependencies {
-                let binding_quote = if is_new {
-                    quote! {
-                        let #fate_ident = Fate::from_expression
-                    }
+                let (binding_quote, result_quote) = if is_new {
+                    (
+                        quote! {
+                            let #fate_ident = Fate::from_expression
+                        },
+                        quote! {},
+                    )
                 } else {
-                    quote! {
-                        #fate_ident.bind_expression
-                    }
+                    (
+                        quote! {
+                            #fate_ident.bind_expression
+                        },
+                        quote! { .unwrap() },
+                    )
                 };
                 quote! {
                     #clones;
                     #binding_quote(
-                        Box::new(move || {#value_expr #fixed_expr}), vec![#dependencies]);
+                        Box::new(move || {#value_expr #fixed_expr}), vec![#dependencies])#result_quote;
                 }
             } else {
                 let binding_quote = if is_new {
@@ -129,6 +226,17 @@ impl Parse for Fate {
     }
 }
 
+/// Declares or reassigns a set of `Fate`s from plain-looking `let`/assignment
+/// statements, rewriting reads of the names listed in `[...]` into `.get()`
+/// calls and wiring up dependencies automatically.
+///
+/// A reassignment (`name = expr;`, as opposed to `let name = expr;`) that
+/// depends on any of the bracketed names expands to a `bind_expression`
+/// call. If that reassignment would close a dependency cycle back onto
+/// itself, the generated code `.unwrap()`s the resulting `Err(CycleError)`
+/// and panics, since there's no way to propagate a `Result` out of a plain
+/// assignment statement -- avoid reassigning a `Fate` to an expression that
+/// reads something depending on it.
 #[proc_macro]
 pub fn fate(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let Fate { quotes } = parse_macro_input!(input as Fate);